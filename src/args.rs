@@ -10,7 +10,9 @@ use clap::{crate_authors, crate_version, crate_name, crate_description};
 pub enum Output {
   FileName,
   Bytes,
-  Offset
+  Offset,
+  Json,
+  Count
 }
 
 impl Default for Output {
@@ -18,6 +20,17 @@ impl Default for Output {
 }
 
 
+/// Whether input files may be memory-mapped instead of read into a buffer.
+/// Mirrors ripgrep's `MmapChoice`, minus the `Always` variant: bgrep only maps files
+/// when it looks profitable, or never.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum MmapChoice {
+  #[default]
+  Auto,
+  Never
+}
+
+
 /// The values of all flags, except help and version.
 #[derive(Default, Debug)]
 pub struct Options {
@@ -26,7 +39,24 @@ pub struct Options {
   pub trim_ending_newline: bool,
   pub non_matching: bool, // Whether to print non matching files. Only true when (-L).
   pub print_filename: bool,
-  pub output: Output
+  pub output: Output,
+  pub mmap: MmapChoice,
+  pub threads: usize,
+  pub recursive: bool,
+  pub no_ignore: bool,
+  pub hidden: bool,
+  pub search_zip: bool,
+  pub before: usize,
+  pub after: usize,
+  pub max_count: Option<usize>
+}
+
+
+/// Validates that `value` parses as a `usize`, for use as a clap argument validator.
+fn validate_usize(value: String) -> Result<(), String> {
+  value.parse::<usize>()
+       .map(|_| ())
+       .map_err(|e| format!("'{}' is not a valid number: {}", value, e))
 }
 
 
@@ -128,6 +158,8 @@ fn build_app() -> App<'static, 'static> {
           "byte-offset",
           "files-with-matches",
           "files-without-matches",
+          "json",
+          "count",
         ])
     )
     .arg(
@@ -139,6 +171,8 @@ fn build_app() -> App<'static, 'static> {
           "only-matching",
           "files-with-matches",
           "files-without-matches",
+          "json",
+          "count",
         ])
     )
     .arg(
@@ -150,6 +184,8 @@ fn build_app() -> App<'static, 'static> {
           "only-matching",
           "byte-offset",
           "files-without-matches",
+          "json",
+          "count",
         ])
     )
     .arg(
@@ -161,8 +197,110 @@ fn build_app() -> App<'static, 'static> {
           "only-matching",
           "byte-offset",
           "files-with-matches",
+          "json",
+          "count",
         ])
     )
+    .arg(
+      Arg::with_name("json")
+        .long("json")
+        .help("Prints matches as JSON Lines, one object per match, for machine consumption")
+        .overrides_with_all(&[
+          "only-matching",
+          "byte-offset",
+          "files-with-matches",
+          "files-without-matches",
+          "count",
+        ])
+    )
+    .arg(
+      Arg::with_name("count")
+        .short("c")
+        .long("count")
+        .help("Prints the number of matches per file, instead of the matches themselves")
+        .overrides_with_all(&[
+          "only-matching",
+          "byte-offset",
+          "files-with-matches",
+          "files-without-matches",
+          "json",
+        ])
+    )
+    .arg(
+      Arg::with_name("max-count")
+        .long("max-count")
+        .takes_value(true)
+        .validator(validate_usize)
+        .help("Stop counting after N matches per file (count output mode only)")
+    )
+    .arg(
+      Arg::with_name("mmap")
+        .long("mmap")
+        .takes_value(true)
+        .possible_values(&["auto", "never"])
+        .default_value("auto")
+        .help("Whether to memory-map input files instead of reading them into a buffer")
+    )
+    .arg(
+      Arg::with_name("threads")
+        .short("j")
+        .long("threads")
+        .takes_value(true)
+        .validator(validate_usize)
+        .help("Number of worker threads to search files with (default: number of CPUs)")
+    )
+    // Traversal flags:
+    .arg(
+      Arg::with_name("recursive")
+        .short("r")
+        .long("recursive")
+        .help("Recursively search files in directories given in <files>")
+    )
+    .arg(
+      Arg::with_name("no-ignore")
+        .long("no-ignore")
+        .help("Don't respect .gitignore/.ignore files when searching recursively")
+    )
+    .arg(
+      Arg::with_name("hidden")
+        .long("hidden")
+        .help("Search hidden files and directories when searching recursively")
+    )
+    .arg(
+      Arg::with_name("search-zip")
+        .short("z")
+        .long("search-zip")
+        .help("Search the decompressed contents of gzip, bzip2, xz and zstd files, detected by extension")
+    )
+    // Context flags:
+    .arg(
+      Arg::with_name("after-bytes")
+        .short("A")
+        .long("after-bytes")
+        .takes_value(true)
+        .validator(validate_usize)
+        .conflicts_with("invert-match")
+        .help("Prints N bytes of trailing context after each match (bytes and byte-offset output modes only)")
+    )
+    .arg(
+      Arg::with_name("before-bytes")
+        .short("B")
+        .long("before-bytes")
+        .takes_value(true)
+        .validator(validate_usize)
+        .conflicts_with("invert-match")
+        .help("Prints N bytes of leading context before each match (bytes and byte-offset output modes only)")
+    )
+    .arg(
+      Arg::with_name("context-bytes")
+        .short("C")
+        .long("context-bytes")
+        .takes_value(true)
+        .validator(validate_usize)
+        .conflicts_with("invert-match")
+        .overrides_with_all(&["after-bytes", "before-bytes"])
+        .help("Prints N bytes of context on both sides of each match (bytes and byte-offset output modes only)")
+    )
 }
 
 
@@ -184,15 +322,38 @@ fn build_args(args: ArgMatches) -> Args {
     flag("only-matching"),
     flag("byte-offset"),
     flag("files-with-matches"),
-    flag("files-without-matches")
+    flag("files-without-matches"),
+    flag("json"),
+    flag("count")
   );
 
   let output = match output_flags {
-    (true, _, _, _) => Output::Bytes,
-    (_, true, _, _) => Output::Offset,
-    (_, _, true, _) => Output::FileName,
-    (_, _, _, true) => Output::FileName,
-    (_, _, _, _)    => Default::default(),
+    (true, _, _, _, _, _) => Output::Bytes,
+    (_, true, _, _, _, _) => Output::Offset,
+    (_, _, true, _, _, _) => Output::FileName,
+    (_, _, _, true, _, _) => Output::FileName,
+    (_, _, _, _, true, _) => Output::Json,
+    (_, _, _, _, _, true) => Output::Count,
+    (_, _, _, _, _, _)    => Default::default(),
+  };
+
+  let mmap = match args.value_of("mmap") {
+    Some("never") => MmapChoice::Never,
+    _             => MmapChoice::Auto,
+  };
+
+  let threads = args.value_of("threads")
+                    .map(|v| v.parse().expect("<threads> validated as usize"))
+                    .unwrap_or_else(num_cpus::get);
+
+  let parse_usize = |v: &str| v.parse::<usize>().expect("validated as usize");
+
+  let (before, after) = match args.value_of("context-bytes").map(parse_usize) {
+    Some(n) => (n, n),
+    None => (
+      args.value_of("before-bytes").map(parse_usize).unwrap_or(0),
+      args.value_of("after-bytes").map(parse_usize).unwrap_or(0),
+    )
   };
 
   Args {
@@ -202,7 +363,16 @@ fn build_args(args: ArgMatches) -> Args {
       trim_ending_newline: flag("trim-ending-newline"),
       non_matching: flag("files-without-matches"),
       print_filename: flag("with-filename") || !(flag("no-filename") || files.len() == 1),
-      output
+      output,
+      mmap,
+      threads,
+      recursive: flag("recursive"),
+      no_ignore: flag("no-ignore"),
+      hidden: flag("hidden"),
+      search_zip: flag("search-zip"),
+      before,
+      after,
+      max_count: args.value_of("max-count").map(parse_usize)
     },
     pattern,
     files
@@ -4,6 +4,7 @@ use std::fs::File;
 use std::path::{Path, PathBuf};
 use std::fmt::Display;
 
+use memmap2::Mmap;
 use regex::bytes::{Regex, RegexBuilder};
 
 use crate::args::{self, Args};
@@ -78,7 +79,30 @@ fn grep_filename<O: Write, P: Display, B: AsRef<[u8]>>(
 }
 
 
+/// Compute the context window around each match in `buffer`, expanding `[start, end)`
+/// by `before`/`after` bytes on either side, then merging windows that end up
+/// overlapping or adjacent (`next.start <= current.end`) so overlapping contexts are
+/// never emitted twice.
+fn context_windows(buffer: &[u8], pattern: &Regex, before: usize, after: usize) -> Vec<(usize, usize)> {
+  let mut windows: Vec<(usize, usize)> = Vec::new();
+
+  for m in pattern.find_iter(buffer) {
+    let start = m.start().saturating_sub(before);
+    let end = (m.end() + after).min(buffer.len());
+
+    match windows.last_mut() {
+      Some((_, last_end)) if start <= *last_end => *last_end = end.max(*last_end),
+      _ => windows.push((start, end))
+    }
+  }
+
+  windows
+}
+
+
 /// Run bgrep, outputting the matched bytes to the given `out`.
+/// When `options.before`/`options.after` are set, prints the context window around
+/// each match instead of just the match itself, merging overlapping windows.
 /// Returns whether there was a match.
 fn grep_bytes<O: Write, P: Display, B: AsRef<[u8]>>(
   out: &mut O,
@@ -120,6 +144,15 @@ fn grep_bytes<O: Write, P: Display, B: AsRef<[u8]>>(
       }
     }
   }
+  else if options.before > 0 || options.after > 0 {
+    let windows = context_windows(buffer, pattern, options.before, options.after);
+
+    for (start, end) in &windows {
+      write_bytes(&buffer[*start .. *end])?;
+    }
+
+    matched = !windows.is_empty();
+  }
   else {
     let mut matches = pattern.find_iter(buffer);
 
@@ -141,6 +174,8 @@ fn grep_bytes<O: Write, P: Display, B: AsRef<[u8]>>(
 
 
 /// Run bgrep, outputting the matche's offset in hex to the given `out`.
+/// When `options.before`/`options.after` are set, reports the merged context window's
+/// start offset instead of the bare match's.
 /// Returns whether there was a match.
 fn grep_offset<O: Write, P: Display, B: AsRef<[u8]>>(
   out: &mut O,
@@ -160,8 +195,6 @@ fn grep_offset<O: Write, P: Display, B: AsRef<[u8]>>(
   };
 
 
-  let mut matches = pattern.find_iter(buffer);
-
   let mut matched = false;
 
   if options.inverse {
@@ -169,7 +202,7 @@ fn grep_offset<O: Write, P: Display, B: AsRef<[u8]>>(
     // inverse match is present.
     let mut end = 0; // Start from the beginning of the buffer.
 
-    for m in matches {
+    for m in pattern.find_iter(buffer) {
       if m.start() > end {
         write_hex(end)?;
         matched = true;
@@ -183,7 +216,17 @@ fn grep_offset<O: Write, P: Display, B: AsRef<[u8]>>(
       matched = true;
     }
   }
+  else if options.before > 0 || options.after > 0 {
+    let windows = context_windows(buffer, pattern, options.before, options.after);
+
+    for (start, _) in &windows {
+      write_hex(*start)?;
+      matched = true;
+    }
+  }
   else {
+    let mut matches = pattern.find_iter(buffer);
+
     // Set `matched` if there is a first occurrence:
     if let Some(m) = matches.next() {
       write_hex(m.start())?;
@@ -201,6 +244,234 @@ fn grep_offset<O: Write, P: Display, B: AsRef<[u8]>>(
 }
 
 
+/// Returns `buffer` with the trailing newline dropped, if `trim` is set and present.
+/// Operates on a view rather than mutating `buffer`, so it works equally for an owned
+/// read buffer and for a memory-mapped, read-only file.
+fn trim_ending_newline(buffer: &[u8], trim: bool) -> &[u8] {
+  if trim && buffer.last() == Some(&b'\n') {
+    &buffer[.. buffer.len() - 1]
+  } else {
+    buffer
+  }
+}
+
+
+/// Dispatch to the grep function for `options.output`.
+fn dispatch<O: Write, P: Display>(
+  out: &mut O,
+  options: &args::Options,
+  pattern: &Regex,
+  path: P,
+  buffer: &[u8]
+) -> io::Result<bool> {
+  match options.output {
+    args::Output::FileName => grep_filename (out, options, pattern, path, buffer),
+    args::Output::Bytes    => grep_bytes    (out, options, pattern, path, buffer),
+    args::Output::Offset   => grep_offset   (out, options, pattern, path, buffer),
+    args::Output::Json     => grep_json     (out, options, pattern, path, buffer),
+    args::Output::Count    => grep_count    (out, options, pattern, path, buffer)
+  }
+}
+
+
+/// A compression format recognized by `-z/--search-zip`.
+enum Compression {
+  Gzip,
+  Bzip2,
+  Xz,
+  Zstd
+}
+
+
+/// Detect the compression format of `path` from its extension.
+fn detect_compression(path: &Path) -> Option<Compression> {
+  match path.extension().and_then(|ext| ext.to_str()) {
+    Some("gz")  => Some(Compression::Gzip),
+    Some("bz2") => Some(Compression::Bzip2),
+    Some("xz")  => Some(Compression::Xz),
+    Some("zst") => Some(Compression::Zstd),
+    _ => None
+  }
+}
+
+
+/// Stream-decompress `file` as `compression` into `buffer`, appending to any existing
+/// content (the caller is expected to have cleared `buffer` beforehand).
+fn decompress_into(file: &File, compression: Compression, buffer: &mut Vec<u8>) -> io::Result<()> {
+  // Each decoder takes `file` by value; clone the handle (a cheap fd dup) so the
+  // caller keeps its own `File` usable afterwards.
+  let file = file.try_clone()?;
+
+  match compression {
+    Compression::Gzip  => flate2::read::GzDecoder::new(file).read_to_end(buffer),
+    Compression::Bzip2 => bzip2::read::BzDecoder::new(file).read_to_end(buffer),
+    Compression::Xz    => xz2::read::XzDecoder::new(file).read_to_end(buffer),
+    Compression::Zstd  => zstd::Decoder::new(file)?.read_to_end(buffer)
+  }?;
+
+  Ok(())
+}
+
+
+/// Attempt to memory-map `file`, following `choice`. Only regular files whose size is
+/// known and above a minimum threshold are mapped, since mapping tiny files is not
+/// worth the syscall overhead. Mapping failure (e.g. a zero-length file, or a
+/// filesystem that does not support it) is treated as a soft fallback: `None` tells the
+/// caller to fall back to a buffered read.
+///
+/// Safety caveat: if another process truncates the file while it is mapped, touching
+/// the truncated-away region raises `SIGBUS`. bgrep does not attempt to catch this,
+/// matching ripgrep's documented caveat for the same tradeoff.
+fn try_mmap(file: &File, file_size: u64, choice: args::MmapChoice) -> Option<Mmap> {
+  const MIN_MMAP_SIZE: u64 = 16 * 1024;
+
+  if !matches!(choice, args::MmapChoice::Auto) || file_size < MIN_MMAP_SIZE {
+    return None;
+  }
+
+  unsafe { Mmap::map(file) }.ok()
+}
+
+
+/// Escape `s` as a JSON string, including the surrounding quotes.
+/// Rust's `Debug` escaping (`{:?}`) is not a substitute: it renders control characters
+/// as `\u{X}` (braced, variable-width), whereas JSON requires `\uXXXX` (unbraced,
+/// exactly 4 hex digits).
+fn json_escape(s: &str) -> String {
+  let mut escaped = String::with_capacity(s.len() + 2);
+  escaped.push('"');
+
+  for c in s.chars() {
+    match c {
+      '"'  => escaped.push_str("\\\""),
+      '\\' => escaped.push_str("\\\\"),
+      '\n' => escaped.push_str("\\n"),
+      '\r' => escaped.push_str("\\r"),
+      '\t' => escaped.push_str("\\t"),
+      c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+      c => escaped.push(c)
+    }
+  }
+
+  escaped.push('"');
+  escaped
+}
+
+
+/// Run bgrep, outputting one JSON object per match to the given `out`, for machine
+/// consumption. Each object carries the file path, the match's byte offset and length,
+/// and the matched bytes themselves, base64-encoded since matches are arbitrary binary.
+/// Returns whether there was a match.
+fn grep_json<O: Write, P: Display, B: AsRef<[u8]>>(
+  out: &mut O,
+  options: &args::Options,
+  pattern: &Regex,
+  path: P,
+  buffer: B,
+) -> io::Result<bool> {
+  let buffer = buffer.as_ref();
+  let path = json_escape(&path.to_string());
+
+  let mut write_match = |offset: usize, bytes: &[u8]| {
+    writeln!(
+      out,
+      r#"{{"path":{},"offset":{},"length":{},"bytes":"{}"}}"#,
+      path,
+      offset,
+      bytes.len(),
+      base64::encode(bytes)
+    )
+  };
+
+  let mut matched = false;
+
+  if options.inverse {
+    // Same "hole" detection as `grep_offset`, reporting the gap's bytes alongside it.
+    let mut end = 0; // Start from the beginning of the buffer.
+
+    for m in pattern.find_iter(buffer) {
+      if m.start() > end {
+        write_match(end, &buffer[end .. m.start()])?;
+        matched = true;
+      }
+
+      end = m.end();
+    }
+
+    if end < buffer.len() { // Also check for a "hole" after the last match.
+      write_match(end, &buffer[end ..])?;
+      matched = true;
+    }
+  }
+  else {
+    for m in pattern.find_iter(buffer) {
+      write_match(m.start(), m.as_bytes())?;
+      matched = true;
+    }
+  }
+
+  Ok(matched)
+}
+
+
+/// Run bgrep, outputting the number of matches in `path` to the given `out`, instead
+/// of the matches themselves. When `options.max_count` is set, counting (and the
+/// underlying iteration) stops as soon as that many matches have been found.
+/// Returns whether there was at least one match.
+fn grep_count<O: Write, P: Display, B: AsRef<[u8]>>(
+  out: &mut O,
+  options: &args::Options,
+  pattern: &Regex,
+  path: P,
+  buffer: B,
+) -> io::Result<bool> {
+  let buffer = buffer.as_ref();
+
+  let count = if options.inverse {
+    // Count "holes": gaps between matches, plus any trailing gap, mirroring the
+    // hole-detection logic in `grep_offset`. The cap is checked *before* counting each
+    // hole, so `max_count == Some(0)` means "count nothing" instead of only kicking in
+    // after the first over-count.
+    let mut end = 0; // Start from the beginning of the buffer.
+    let mut count = 0;
+
+    for m in pattern.find_iter(buffer) {
+      if options.max_count.is_some_and(|max| count >= max) {
+        break;
+      }
+
+      if m.start() > end {
+        count += 1;
+      }
+
+      end = m.end();
+    }
+
+    if end < buffer.len() && options.max_count.is_none_or(|max| count < max) {
+      count += 1;
+    }
+
+    count
+  }
+  else {
+    let matches = pattern.find_iter(buffer);
+
+    match options.max_count {
+      Some(max) => matches.take(max).count(),
+      None => matches.count()
+    }
+  };
+
+  if options.print_filename {
+    writeln!(out, "{}: {}", path, count)?;
+  } else {
+    writeln!(out, "{}", count)?;
+  }
+
+  Ok(count > 0)
+}
+
+
 /// Run bgrep with the given options, outputting to the given `out`.
 /// Error detail may be outputted to stderr.
 /// Returns whether there was a match.
@@ -216,52 +487,212 @@ fn run_file<O: Write, P: AsRef<Path>, B: AsMut<Vec<u8>>>(
 
   buffer.clear();
 
-  let (read_result, path) =
-    if path == Path::new(args::STDIN) { // Path::new is cost-free.
-      (io::stdin().lock().read_to_end(buffer), Path::new("<stdin>").display())
+  if path == Path::new(args::STDIN) { // Path::new is cost-free.
+    if let Err(e) = io::stdin().lock().read_to_end(buffer) {
+      eprintln!("Error: failed to read file '<stdin>'");
+      return Err(e);
     }
-    else {
-      let mut file = File::open(path)
-                          .map_err(|e| {
-                            eprintln!("Error: failed to open file '{}'", path.display());
-                            e
-                          })?;
-
-      // Resize buffer to the file size if it exceeds the current size.
-      // Currently, the strategy is to grow if needed, and otherwise do nothing.
-      // Considering we never shrink the buffer, this can be bad if the first file
-      // is huge and the others are small.
-      let file_size = file.metadata()
-                          .map(|m| m.len())
-                          .unwrap_or(0) as usize;
-      buffer.reserve(
-        file_size.saturating_sub(buffer.len())
-      );
-
-      (file.read_to_end(buffer), path.display())
-    };
 
-  if let Err(e) = read_result {
-    eprintln!("Error: failed to read file '{}'", path);
+    let content = trim_ending_newline(buffer, options.trim_ending_newline);
+    return dispatch(out, options, pattern, Path::new("<stdin>").display(), content);
+  }
+
+  let mut file = File::open(path)
+                      .map_err(|e| {
+                        eprintln!("Error: failed to open file '{}'", path.display());
+                        e
+                      })?;
+
+  if options.search_zip {
+    if let Some(compression) = detect_compression(path) {
+      if let Err(e) = decompress_into(&file, compression, buffer) {
+        eprintln!("Error: failed to decompress file '{}'", path.display());
+        return Err(e);
+      }
+
+      let content = trim_ending_newline(buffer, options.trim_ending_newline);
+      return dispatch(out, options, pattern, path.display(), content);
+    }
+  }
+
+  let file_size = file.metadata()
+                      .map(|m| m.len())
+                      .unwrap_or(0);
+
+  if let Some(mmap) = try_mmap(&file, file_size, options.mmap) {
+    let content = trim_ending_newline(&mmap, options.trim_ending_newline);
+    return dispatch(out, options, pattern, path.display(), content);
+  }
+
+  // Resize buffer to the file size if it exceeds the current size.
+  // Currently, the strategy is to grow if needed, and otherwise do nothing.
+  // Considering we never shrink the buffer, this can be bad if the first file
+  // is huge and the others are small.
+  buffer.reserve(
+    (file_size as usize).saturating_sub(buffer.len())
+  );
+
+  if let Err(e) = file.read_to_end(buffer) {
+    eprintln!("Error: failed to read file '{}'", path.display());
     return Err(e);
   }
 
+  let content = trim_ending_newline(buffer, options.trim_ending_newline);
+  dispatch(out, options, pattern, path.display(), content)
+}
 
-  // Trim the ending newline if requested and present:
-  if options.trim_ending_newline && buffer.last() == Some(&b'\n') {
-    buffer.pop();
-  };
+/// Run bgrep over `files` using up to `options.threads` worker threads.
+/// Each worker owns its own reusable read buffer and its own private output buffer, so
+/// the greps themselves run fully independently; completed per-file output is flushed
+/// to `out` only once every file ahead of it in `files` has already been flushed, so
+/// results stay in input order regardless of which worker finishes first.
+///
+/// Aggregates the exit-code logic the same way the serial loop in `run` does: returns
+/// whether there was any match, preserving the last I/O error's kind. A `BrokenPipe`
+/// from any worker stops dispatching further files early and counts as a match.
+fn run_parallel<O: Write>(
+  options: &args::Options,
+  pattern: &Regex,
+  files: &[PathBuf],
+  out: &mut O
+) -> io::Result<bool> {
+  use std::collections::HashMap;
+  use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+  use std::sync::mpsc;
 
+  let threads = options.threads.max(1);
 
-  let matched = match options.output {
-    args::Output::FileName => grep_filename (out, options, pattern, path, buffer),
-    args::Output::Bytes    => grep_bytes    (out, options, pattern, path, buffer),
-    args::Output::Offset   => grep_offset   (out, options, pattern, path, buffer)
-  }?;
+  let next_index = AtomicUsize::new(0);
+  // Set once the main thread observes `BrokenPipe`, so workers stop claiming new files
+  // instead of grinding through the rest of the list after dispatch should have stopped.
+  let cancelled = AtomicBool::new(false);
+  let (tx, rx) = mpsc::channel::<(usize, io::Result<(bool, Vec<u8>)>)>();
 
-  Ok(matched)
+  std::thread::scope(|scope| {
+    for _ in 0 .. threads {
+      let tx = tx.clone();
+      let next_index = &next_index;
+      let cancelled = &cancelled;
+
+      scope.spawn(move || {
+        let mut buffer = Vec::<u8>::new();
+
+        loop {
+          if cancelled.load(Ordering::Relaxed) {
+            break;
+          }
+
+          let index = next_index.fetch_add(1, Ordering::Relaxed);
+
+          if index >= files.len() {
+            break;
+          }
+
+          let mut worker_out = Vec::<u8>::new();
+          let result = run_file(&mut worker_out, options, pattern, &files[index], &mut buffer)
+                        .map(|matched| (matched, worker_out));
+
+          if tx.send((index, result)).is_err() {
+            break; // The main thread stopped receiving, e.g. after a `BrokenPipe` bail-out.
+          }
+        }
+      });
+    }
+
+    drop(tx); // Drop our own handle, so `rx` ends once every worker has finished.
+
+    // Out-of-order completions are held here until their predecessors have flushed.
+    let mut pending: HashMap<usize, io::Result<(bool, Vec<u8>)>> = HashMap::new();
+    let mut next_to_flush = 0;
+    let mut result = Ok(false);
+
+    while next_to_flush < files.len() {
+      let item = match pending.remove(&next_to_flush) {
+        Some(item) => item,
+        None => match rx.recv() {
+          Ok((index, item)) if index == next_to_flush => item,
+          Ok((index, item)) => {
+            pending.insert(index, item);
+            continue;
+          }
+          Err(_) => break, // Every worker finished early, e.g. after a `BrokenPipe` bail-out.
+        }
+      };
+
+      next_to_flush += 1;
+
+      match item {
+        Ok((matched, bytes)) => match out.write_all(&bytes) {
+          Ok(()) =>
+            if matched {
+              result = result.map(|_| true);
+            },
+          Err(e) if e.kind() == io::ErrorKind::BrokenPipe => {
+            cancelled.store(true, Ordering::Relaxed); // Stop workers from claiming more files.
+            result = result.map(|_| true);            // Output only happens on a match.
+            break;
+          }
+          Err(e) => result = Err(e) // Store the error and move on.
+        },
+        Err(e) =>
+          if e.kind() == io::ErrorKind::BrokenPipe {
+            cancelled.store(true, Ordering::Relaxed); // Stop workers from claiming more files.
+            result = result.map(|_| true);            // `BrokenPipe` only happens when outputting,
+            break;                                    // and that means there was a match.
+          } else {
+            result = Err(e) // Store the error and move on.
+          }
+      }
+    }
+
+    result
+  })
+}
+
+
+/// Expand `files` into concrete file paths.
+/// When `options.recursive` is unset, `files` is returned unchanged (including `-` for
+/// stdin). Otherwise, every entry that is a directory is walked with the `ignore`
+/// crate's `WalkBuilder`, honoring `.gitignore`/`.ignore` files unless `--no-ignore` was
+/// given, and skipping hidden entries unless `--hidden` was given. Entries encountered
+/// while walking that are not regular files (other directories, sockets, ...) are
+/// skipped rather than erroring; non-directory entries in `files` are passed through
+/// untouched, so `run_file` can report e.g. a missing path as it does today.
+fn expand_files(options: &args::Options, files: &[PathBuf]) -> Vec<PathBuf> {
+  if !options.recursive {
+    return files.to_vec();
+  }
+
+  let mut expanded = Vec::with_capacity(files.len());
+
+  for file in files {
+    if file.as_path() != Path::new(args::STDIN) && file.is_dir() {
+      let mut builder = ignore::WalkBuilder::new(file);
+      builder.git_ignore(!options.no_ignore)
+             .git_global(!options.no_ignore)
+             .git_exclude(!options.no_ignore)
+             .ignore(!options.no_ignore)
+             .hidden(!options.hidden);
+
+      for entry in builder.build() {
+        match entry {
+          Ok(entry) =>
+            if entry.file_type().is_some_and(|ft| ft.is_file()) {
+              expanded.push(entry.into_path());
+            },
+          Err(e) => eprintln!("Error: failed to walk '{}': {}", file.display(), e)
+        }
+      }
+    }
+    else {
+      expanded.push(file.clone());
+    }
+  }
+
+  expanded
 }
 
+
 /// Run bgrep with the given args, outputting to stdout.
 /// Error detail may be outputted to stderr.
 /// Returns whether there was a match.
@@ -277,6 +708,15 @@ pub fn run<O: Write>(args: Args, out: &mut O) -> io::Result<bool> {
     }
   )?;
 
+  let files = expand_files(&options, &files);
+
+
+  // Parallelizing a single file brings no benefit and only adds thread-pool overhead,
+  // and `threads == 1` must behave exactly like the serial loop below.
+  if options.threads > 1 && files.len() > 1 {
+    return run_parallel(&options, &pattern, &files, out);
+  }
+
 
   // Reuse the same buffer for all the files, minimizing allocations.
   let mut buffer = Vec::<u8>::new();
@@ -296,10 +736,7 @@ pub fn run<O: Write>(args: Args, out: &mut O) -> io::Result<bool> {
   // We need to store the last generated error if any, or whether there was a match:
   let mut result = Ok(false);
 
-  // Converting to vec to use the owned iterator. Box<[T]> has no owned iterator.
-  for file in files.to_vec() {
-    let file: PathBuf = file; // Make sure we are using an owned iterator.
-
+  for file in files {
     match run_file(out, &options, &pattern, &file, &mut buffer) {
       Ok(false) => (),
       Ok(true) => result = result.map(|_| true), // Set to true if there was no error.
@@ -316,3 +753,85 @@ pub fn run<O: Write>(args: Args, out: &mut O) -> io::Result<bool> {
 
   result
 }
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::fs;
+
+  /// Writes `contents` to a fresh file under the system temp dir and returns its path.
+  fn write_temp_file(name: &str, contents: &[u8]) -> PathBuf {
+    let mut path = std::env::temp_dir();
+    path.push(format!("bgrep-test-{}-{}", std::process::id(), name));
+    fs::write(&path, contents).unwrap();
+    path
+  }
+
+  #[test]
+  fn run_parallel_flushes_matches_in_input_order() {
+    let files = vec![
+      write_temp_file("order-a", b"xAAAx"),
+      write_temp_file("order-b", b"xBBBx"),
+      write_temp_file("order-c", b"xCCCx"),
+    ];
+
+    let options = args::Options { threads: 3, output: args::Output::Bytes, ..Default::default() };
+    let pattern = build_pattern(&"[A-Z]{3}", &options).unwrap();
+
+    let mut out = Vec::new();
+    let matched = run_parallel(&options, &pattern, &files, &mut out).unwrap();
+
+    for file in &files {
+      let _ = fs::remove_file(file);
+    }
+
+    assert!(matched);
+    assert_eq!(out, b"AAA\nBBB\nCCC\n");
+  }
+
+  #[test]
+  fn run_parallel_preserves_last_error_kind_over_later_matches() {
+    let matching = write_temp_file("err-match", b"needle");
+    let missing = PathBuf::from("/nonexistent/path-for-bgrep-test");
+
+    let files = vec![missing, matching.clone()];
+    let options = args::Options { threads: 2, ..Default::default() };
+    let pattern = build_pattern(&"needle", &options).unwrap();
+
+    let mut out = Vec::new();
+    let result = run_parallel(&options, &pattern, &files, &mut out);
+
+    let _ = fs::remove_file(&matching);
+
+    assert_eq!(result.unwrap_err().kind(), io::ErrorKind::NotFound);
+  }
+
+  #[test]
+  fn grep_count_inverse_max_count_zero_counts_nothing() {
+    let options = args::Options { inverse: true, max_count: Some(0), ..Default::default() };
+    let pattern = build_pattern(&"XX", &options).unwrap();
+
+    let mut out = Vec::new();
+    let matched = grep_count(&mut out, &options, &pattern, "path", &b"aaXXbbXXcc"[..]).unwrap();
+
+    assert!(!matched);
+    assert_eq!(String::from_utf8(out).unwrap(), "0\n");
+  }
+
+  #[test]
+  fn grep_count_inverse_respects_max_count() {
+    let buffer = &b"aaXXbbXXccXXdd"[..]; // 3 "holes": "aa", "bb", "cc", plus trailing "dd".
+
+    for max in 0 ..= 4 {
+      let inverse_options = args::Options { inverse: true, max_count: Some(max), ..Default::default() };
+      let inverse_pattern = build_pattern(&"XX", &inverse_options).unwrap();
+
+      let mut inverse_out = Vec::new();
+      grep_count(&mut inverse_out, &inverse_options, &inverse_pattern, "path", buffer).unwrap();
+      let inverse_count: usize = String::from_utf8(inverse_out).unwrap().trim().parse().unwrap();
+
+      assert_eq!(inverse_count, max.min(4));
+    }
+  }
+}